@@ -14,31 +14,93 @@
 
 use std::vec::Vec;
 
+#[cfg(feature = "generic_calculation")]
+mod modular;
+#[cfg(feature = "generic_calculation")]
+pub use modular::Mod;
+
 pub enum GaussEliminationOption {
     JustEchelon,
     PrepareReduce
 }
 
-// matrix is represented as vector of rows, a row being a vector of columns.
-pub fn gauss_elimination(matrix: &mut Vec<Vec<f32>>, option: GaussEliminationOption) {
-    fn find_pivot(matrix: &mut Vec<Vec<f32>>, d: usize) -> Option<usize> {
-        return (d..matrix.len()).find(|&i| matrix[i][d] != 0f32);
+// Strategy used by `find_pivot` to pick the row that becomes the pivot for
+// a given column. `FirstNonzero` is the historical behavior, exact for
+// rational/modular arithmetic. `PartialPivot` picks the row with the
+// largest absolute value in the column, which keeps f32 round-off small.
+pub enum PivotStrategy {
+    FirstNonzero,
+    PartialPivot,
+}
+
+// Rank, determinant and swap parity of a matrix reduced by
+// `gauss_elimination_with_info` (or internally, by `solve`/`inverse`).
+pub struct GaussEliminationInfo {
+    pub rank: usize,
+    pub determinant: f32,
+    pub sign: i8,
+}
+
+// An entry below this (relative to the matrix's own magnitude) is treated
+// as a failed pivot rather than a genuine, if tiny, one. Mirrors how the
+// `approx` crate scales its comparisons by the operands' magnitude instead
+// of using a fixed absolute threshold. `epsilon` 0.0 recovers the exact
+// `!= 0f32` comparison the original "first non-zero" pivot search used.
+fn pivot_epsilon(matrix: &[Vec<f32>]) -> f32 {
+    let magnitude = matrix
+        .iter()
+        .flatten()
+        .fold(0f32, |acc, &v| acc.max(v.abs()));
+    f32::EPSILON * magnitude.max(1.0)
+}
+
+fn find_pivot(matrix: &[Vec<f32>], d: usize, pivot: &PivotStrategy, epsilon: f32) -> Option<usize> {
+    match pivot {
+        PivotStrategy::FirstNonzero => (d..matrix.len()).find(|&i| matrix[i][d].abs() > epsilon),
+        // `total_cmp` gives NaN entries a well-defined (if arbitrary) place
+        // in the order instead of making `max_by` panic.
+        PivotStrategy::PartialPivot => (d..matrix.len())
+            .filter(|&i| matrix[i][d].abs() > epsilon)
+            .max_by(|&a, &b| matrix[a][d].abs().total_cmp(&matrix[b][d].abs())),
     }
+}
+
+// Shared elimination loop behind `gauss_elimination`, `gauss_elimination_with_pivot`
+// and `gauss_elimination_with_info`: reduce to echelon form (optionally
+// normalizing pivots to 1.0), picking pivots via `pivot` and treating entries
+// with absolute value at or below `epsilon` as zero, and report the rank,
+// determinant and swap parity of the reduction. A rank deficient matrix
+// reports `determinant: 0.0`, rather than the product of whatever partial
+// pivots were found.
+fn gauss_elimination_core(
+    matrix: &mut [Vec<f32>],
+    option: GaussEliminationOption,
+    pivot: PivotStrategy,
+    epsilon: f32,
+) -> GaussEliminationInfo {
     let nrows = matrix.len();
+    let mut rank = 0;
+    let mut determinant = 1.0f32;
+    let mut sign: i8 = 1;
     for c in 0..nrows {
-        match find_pivot(matrix, c) {
+        match find_pivot(matrix, c, &pivot, epsilon) {
             None => {}
             Some(i) => {
-                for row in i + 1..nrows {
-                    let factor = matrix[row][c] / matrix[i][c];
-                    for col in c..matrix[row].len() {
-                        matrix[row][col] -= factor * matrix[i][col]
-                    }
-                }
-                // Move pivot to row c, in order to get a "real" echelon form.
+                // Move pivot to row c first: with partial pivoting the rows
+                // between c and i are not guaranteed to already be zero in
+                // column c, so they must go through elimination below too.
                 if c != i {
                     matrix.swap(i, c);
+                    sign = -sign;
                 }
+                for row in c + 1..nrows {
+                    let factor = matrix[row][c] / matrix[c][c];
+                    for col in c..matrix[row].len() {
+                        matrix[row][col] -= factor * matrix[c][col]
+                    }
+                }
+                rank += 1;
+                determinant *= matrix[c][c];
                 if matches!(option, GaussEliminationOption::PrepareReduce) {
                     // normalize the pivot to 1.0
                     let factor = 1.0 / matrix[c][c];
@@ -49,18 +111,54 @@ pub fn gauss_elimination(matrix: &mut Vec<Vec<f32>>, option: GaussEliminationOpt
             }
         }
     }
+    GaussEliminationInfo {
+        rank,
+        determinant: if rank < nrows { 0.0 } else { determinant * sign as f32 },
+        sign,
+    }
 }
 
-pub fn gauss_jordan_elimination(matrix: &mut Vec<Vec<f32>>) {
-    gauss_elimination(matrix, GaussEliminationOption::PrepareReduce);
-    let nrows = matrix.len();
+// matrix is represented as vector of rows, a row being a vector of columns.
+pub fn gauss_elimination(matrix: &mut Vec<Vec<f32>>, option: GaussEliminationOption) {
+    gauss_elimination_with_pivot(matrix, option, PivotStrategy::FirstNonzero);
+}
 
-    fn find_pivot(matrix: &mut Vec<Vec<f32>>, d: usize) -> Option<usize> {
-        return (d..matrix.len()).find(|&i| matrix[d][i] != 0f32);
-    }
+// Same as `gauss_elimination`, but lets the caller choose the pivot
+// strategy. Use `PivotStrategy::PartialPivot` for numerical stability with
+// f32, at the cost of no longer producing the "first non-zero" echelon form.
+pub fn gauss_elimination_with_pivot(
+    matrix: &mut Vec<Vec<f32>>,
+    option: GaussEliminationOption,
+    pivot: PivotStrategy,
+) {
+    gauss_elimination_core(matrix, option, pivot, 0.0);
+}
 
+// Same as `gauss_elimination`, but treats entries with an absolute value
+// below a magnitude-scaled tolerance as zero when picking pivots (instead
+// of the exact `!= 0f32` comparison `gauss_elimination` otherwise uses), and
+// reports the rank of the reduced matrix and the determinant of the matrix as
+// it was passed in, letting callers detect rank deficiency instead of
+// silently getting garbage rows.
+pub fn gauss_elimination_with_info(
+    matrix: &mut Vec<Vec<f32>>,
+    option: GaussEliminationOption,
+) -> GaussEliminationInfo {
+    let epsilon = pivot_epsilon(matrix);
+    gauss_elimination_core(matrix, option, PivotStrategy::FirstNonzero, epsilon)
+}
+
+// The back-substitution half of Gauss-Jordan elimination: given a matrix
+// already in echelon form with pivots normalized to 1.0, clears the entries
+// above each pivot. Entries at or below `epsilon` are treated as zero, same
+// as in `gauss_elimination_core`.
+fn back_substitute(matrix: &mut [Vec<f32>], epsilon: f32) {
+    let nrows = matrix.len();
+    fn find_pivot(matrix: &[Vec<f32>], d: usize, epsilon: f32) -> Option<usize> {
+        (d..matrix.len()).find(|&i| matrix[d][i].abs() > epsilon)
+    }
     for d in (1..nrows).rev() {
-        match find_pivot(matrix, d) {
+        match find_pivot(matrix, d, epsilon) {
             None => {}
             Some(i) => {
                 for row in (0..d).rev() {
@@ -74,11 +172,70 @@ pub fn gauss_jordan_elimination(matrix: &mut Vec<Vec<f32>>) {
     }
 }
 
+pub fn gauss_jordan_elimination(matrix: &mut Vec<Vec<f32>>) {
+    gauss_elimination(matrix, GaussEliminationOption::PrepareReduce);
+    back_substitute(matrix, 0.0);
+}
+
+// Gauss-Jordan elimination with the same magnitude-scaled pivot tolerance as
+// `gauss_elimination_with_info`, returning the rank of the reduced matrix so
+// callers (`solve`, `inverse`) can reject ill-conditioned systems instead of
+// normalizing a noise-sized pivot to a misleadingly clean 1.0.
+fn gauss_jordan_elimination_checked(matrix: &mut [Vec<f32>]) -> usize {
+    let epsilon = pivot_epsilon(matrix);
+    let info =
+        gauss_elimination_core(matrix, GaussEliminationOption::PrepareReduce, PivotStrategy::FirstNonzero, epsilon);
+    back_substitute(matrix, epsilon);
+    info.rank
+}
+
+// Solves the linear system `a * x = b` for `x` by augmenting `a` with `b`
+// and running Gauss-Jordan elimination, then reading the solution out of
+// the last column. Returns `None` when `a` and `b` don't have matching,
+// square dimensions, or when `a` is singular or ill-conditioned (a pivot
+// column has no entry clearly distinguishable from zero at the matrix's own
+// scale, so the corresponding equation can't be satisfied uniquely).
+pub fn solve(a: &mut Vec<Vec<f32>>, b: &[f32]) -> Option<Vec<f32>> {
+    let n = a.len();
+    if b.len() != n || a.iter().any(|row| row.len() != n) {
+        return None;
+    }
+    for (row, &rhs) in a.iter_mut().zip(b.iter()) {
+        row.push(rhs);
+    }
+    let rank = gauss_jordan_elimination_checked(a);
+    if rank < n {
+        return None;
+    }
+    Some(a.iter().map(|row| row[n]).collect())
+}
+
+// Computes the inverse of the `n`x`n` matrix `a` by augmenting it with the
+// identity matrix and running Gauss-Jordan elimination; the reduced right
+// half of the augmented matrix is the inverse. Returns `None` if `a` isn't
+// square, or is singular or ill-conditioned.
+pub fn inverse(a: &mut Vec<Vec<f32>>) -> Option<Vec<Vec<f32>>> {
+    let n = a.len();
+    if a.iter().any(|row| row.len() != n) {
+        return None;
+    }
+    for (i, row) in a.iter_mut().enumerate() {
+        for j in 0..n {
+            row.push(if i == j { 1.0 } else { 0.0 });
+        }
+    }
+    let rank = gauss_jordan_elimination_checked(a);
+    if rank < n {
+        return None;
+    }
+    Some(a.iter().map(|row| row[n..].to_vec()).collect())
+}
 
-#[cfg(feature = "generic_calculation")] 
+
+#[cfg(feature = "generic_calculation")]
 pub fn gauss_jordan_elimination_generic<T>(matrix: &mut Vec<Vec<T>>)
-where T: num::traits::One + num::traits::Zero + std::ops::AddAssign + std::ops::SubAssign 
-+ std::ops::Mul<Output = T> + std::ops::MulAssign + std::ops::Div<Output = T> + Clone + Copy 
+where T: num::traits::One + num::traits::Zero + std::ops::AddAssign + std::ops::SubAssign
++ std::ops::Mul<Output = T> + std::ops::MulAssign + std::ops::Div<Output = T> + Clone
 + for<'a> std::ops::Add<&'a T> + for<'a> std::ops::Mul<&'a T, Output =  T>,
 for<'a> &'a T: std::ops::Mul<T, Output = T>{
 
@@ -95,10 +252,10 @@ for d in (1..nrows).rev() {
         None => {}
         Some(i) => {
             for row in (0..d).rev() {
-                let factor = matrix[row][i];
+                let factor = matrix[row][i].clone();
                 for col in d..matrix[i].len() {
-                    let number = matrix[d][col];
-                    matrix[row][col] -= factor * number
+                    let number = matrix[d][col].clone();
+                    matrix[row][col] -= factor.clone() * number
                 }
             }
         }
@@ -110,10 +267,10 @@ for d in (1..nrows).rev() {
 
 //
 
-#[cfg(feature = "generic_calculation")] 
-pub fn gauss_elimination_generic<T>(matrix: &mut Vec<Vec<T>>, option: GaussEliminationOption) 
-where T: num::traits::One + num::traits::Zero + std::ops::AddAssign + std::ops::SubAssign 
-+ std::ops::Mul<Output = T> + std::ops::MulAssign + std::ops::Div<Output = T> + Clone + Copy{
+#[cfg(feature = "generic_calculation")]
+pub fn gauss_elimination_generic<T>(matrix: &mut Vec<Vec<T>>, option: GaussEliminationOption)
+where T: num::traits::One + num::traits::Zero + std::ops::AddAssign + std::ops::SubAssign
++ std::ops::Mul<Output = T> + std::ops::MulAssign + std::ops::Div<Output = T> + Clone {
     fn find_pivot <T: num::traits::Zero >(matrix: &mut Vec<Vec<T>>, d: usize) -> Option<usize> {
         return (d..matrix.len()).find(|&i| matrix[i][d].is_zero() == false);
     }
@@ -123,10 +280,10 @@ where T: num::traits::One + num::traits::Zero + std::ops::AddAssign + std::ops::
             None => {}
             Some(i) => {
                 for row in i + 1..nrows {
-                    let factor = matrix[row][c] / matrix[i][c];
+                    let factor = matrix[row][c].clone() / matrix[i][c].clone();
                     for col in c..matrix[row].len() {
-                        let number = matrix[i][col];
-                        matrix[row][col] -= factor * number
+                        let number = matrix[i][col].clone();
+                        matrix[row][col] -= factor.clone() * number
                     }
                 }
                 // Move pivot to row c, in order to get a "real" echelon form.
@@ -135,16 +292,73 @@ where T: num::traits::One + num::traits::Zero + std::ops::AddAssign + std::ops::
                 }
                 if matches!(option, GaussEliminationOption::PrepareReduce) {
                     // normalize the pivot to 1.0
-                    let factor = T::one() / matrix[c][c];
+                    let factor = T::one() / matrix[c][c].clone();
                     for col in c..matrix[c].len() {
-                        matrix[c][col] *= factor
+                        matrix[c][col] *= factor.clone()
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Same as `gauss_elimination_generic`, but with partial pivoting: the entry
+// with the largest absolute value in the column is chosen as pivot, which
+// keeps round-off small for floating-point scalars. The extra `PartialOrd`
+// `+ num::Signed` bound (needed for `abs()` and comparison) is kept off
+// `gauss_elimination_generic` itself so exact scalars without a notion of
+// sign (e.g. a modular-arithmetic type) can still use the plain version.
+#[cfg(feature = "generic_calculation")]
+pub fn gauss_elimination_generic_with_pivot<T>(
+    matrix: &mut Vec<Vec<T>>,
+    option: GaussEliminationOption,
+)
+where T: num::traits::One + num::traits::Zero + std::ops::AddAssign + std::ops::SubAssign
++ std::ops::Mul<Output = T> + std::ops::MulAssign + std::ops::Div<Output = T> + Clone
++ PartialOrd + num::Signed {
+    fn find_pivot<T>(matrix: &[Vec<T>], d: usize) -> Option<usize>
+    where T: num::traits::Zero + PartialOrd + num::Signed {
+        // `T` only guarantees `PartialOrd`, so two entries can be
+        // incomparable (e.g. a `NaN`-like value); treat that as a tie
+        // instead of panicking via `partial_cmp(...).unwrap()`.
+        (d..matrix.len())
+            .filter(|&i| !matrix[i][d].is_zero())
+            .max_by(|&a, &b| {
+                matrix[a][d]
+                    .abs()
+                    .partial_cmp(&matrix[b][d].abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+    let nrows = matrix.len();
+    for c in 0..nrows {
+        match find_pivot::<T>(matrix, c) {
+            None => {}
+            Some(i) => {
+                // Move pivot to row c first: with partial pivoting the rows
+                // between c and i are not guaranteed to already be zero in
+                // column c, so they must go through elimination below too.
+                if c != i {
+                    matrix.swap(i, c);
+                }
+                for row in c + 1..nrows {
+                    let factor = matrix[row][c].clone() / matrix[c][c].clone();
+                    for col in c..matrix[row].len() {
+                        let number = matrix[c][col].clone();
+                        matrix[row][col] -= factor.clone() * number
+                    }
+                }
+                if matches!(option, GaussEliminationOption::PrepareReduce) {
+                    // normalize the pivot to 1.0
+                    let factor = T::one() / matrix[c][c].clone();
+                    for col in c..matrix[c].len() {
+                        matrix[c][col] *= factor.clone()
                     }
                 }
             }
         }
     }
 }
-    
 
 
 #[cfg(test)]
@@ -223,7 +437,109 @@ mod tests {
     }
 
 
-    #[cfg(feature = "generic_calculation")] 
+    #[test]
+    fn test_gauss_elimination_partial_pivot() {
+        // Column 0 has a larger entry in row 1 than row 0; partial pivoting
+        // swaps row 1 to the top instead of eliminating with the small 1.0.
+        let mut matrix = vec![vec![1.0, 1.0, 5.0], vec![4.0, 2.0, 14.0]];
+        gauss_elimination_with_pivot(
+            &mut matrix,
+            GaussEliminationOption::JustEchelon,
+            PivotStrategy::PartialPivot,
+        );
+        assert_eq!(
+            matrix,
+            vec![vec![4.0, 2.0, 14.0], vec![0.0, 0.5, 1.5]]
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut a = vec![
+            vec![1.0, 2.0, 1.0],
+            vec![2.0, 3.0, 2.0],
+            vec![3.0, 1.0, 4.0],
+        ];
+        let b = vec![10.0, 12.0, 11.0];
+        assert_eq!(solve(&mut a, &b), Some(vec![-27.0, 8.0, 21.0]));
+    }
+
+    #[test]
+    fn test_solve_singular() {
+        let mut a = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        let b = vec![3.0, 5.0];
+        assert_eq!(solve(&mut a, &b), None);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let mut a = vec![vec![2.0, 0.0], vec![0.0, 4.0]];
+        assert_eq!(inverse(&mut a), Some(vec![vec![0.5, 0.0], vec![0.0, 0.25]]));
+    }
+
+    #[test]
+    fn test_inverse_singular() {
+        let mut a = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert_eq!(inverse(&mut a), None);
+    }
+
+    #[test]
+    fn test_solve_mismatched_dimensions() {
+        let mut a = vec![
+            vec![1.0, 2.0, 1.0],
+            vec![2.0, 3.0, 2.0],
+            vec![3.0, 1.0, 4.0],
+        ];
+        let b = vec![10.0, 12.0];
+        assert_eq!(solve(&mut a, &b), None);
+    }
+
+    #[test]
+    fn test_inverse_non_square() {
+        let mut a = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        assert_eq!(inverse(&mut a), None);
+    }
+
+    #[test]
+    fn test_solve_rejects_noisy_near_zero_pivot() {
+        // 1e-20 is exactly nonzero, so the old exact `!= 0f32` pivot test
+        // would accept it, normalize it to a clean 1.0, and return a
+        // "solution" built on a 1e20 blow-up factor. At the matrix's own
+        // scale (values up to 1.0) it's indistinguishable from zero, so the
+        // magnitude-scaled epsilon pivot test correctly reports this as
+        // unsolvable instead.
+        let mut a = vec![vec![1e-20, 0.0], vec![0.0, 1.0]];
+        let b = vec![1.0, 1.0];
+        assert_eq!(solve(&mut a, &b), None);
+    }
+
+    #[test]
+    fn test_gauss_elimination_with_info_full_rank() {
+        let mut matrix = vec![vec![2.0, 0.0], vec![0.0, 4.0]];
+        let info = gauss_elimination_with_info(&mut matrix, GaussEliminationOption::JustEchelon);
+        assert_eq!(info.rank, 2);
+        assert_eq!(info.determinant, 8.0);
+        assert_eq!(info.sign, 1);
+    }
+
+    #[test]
+    fn test_gauss_elimination_with_info_tracks_swap_sign() {
+        let mut matrix = vec![vec![0.0, 1.0], vec![2.0, 3.0]];
+        let info = gauss_elimination_with_info(&mut matrix, GaussEliminationOption::JustEchelon);
+        assert_eq!(info.rank, 2);
+        assert_eq!(info.determinant, -2.0);
+        assert_eq!(info.sign, -1);
+    }
+
+    #[test]
+    fn test_gauss_elimination_with_info_rank_deficient() {
+        let mut matrix = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        let info = gauss_elimination_with_info(&mut matrix, GaussEliminationOption::JustEchelon);
+        assert_eq!(info.rank, 1);
+        assert_eq!(info.determinant, 0.0);
+    }
+
+    #[cfg(feature = "generic_calculation")]
     #[test]
     fn test_gauss_elimination_echelon_generic() {
         let mut matrix = vec![
@@ -242,7 +558,20 @@ mod tests {
         );
     }
 
-    #[cfg(feature = "generic_calculation")] 
+    #[cfg(feature = "generic_calculation")]
+    #[test]
+    fn test_gauss_elimination_generic_partial_pivot() {
+        // Same matrix and expectation as `test_gauss_elimination_partial_pivot`,
+        // exercising the `PartialOrd + num::Signed`-gated generic path.
+        let mut matrix = vec![vec![1.0, 1.0, 5.0], vec![4.0, 2.0, 14.0]];
+        gauss_elimination_generic_with_pivot::<f64>(
+            &mut matrix,
+            GaussEliminationOption::JustEchelon,
+        );
+        assert_eq!(matrix, vec![vec![4.0, 2.0, 14.0], vec![0.0, 0.5, 1.5]]);
+    }
+
+    #[cfg(feature = "generic_calculation")]
     #[test]
     fn test_gauss_elimination_reduce_generic() {
         let mut matrix = vec![
@@ -298,4 +627,29 @@ mod tests {
             ]
         );
     }
+
+    // `BigRational` is not `Copy`, so this exercises the `Clone`-only bound
+    // on the generic elimination path.
+    #[cfg(feature = "generic_calculation")]
+    #[test]
+    fn test_gauss_jordan_elimination_bigrational() {
+        use num::BigRational;
+        fn r(n: i64) -> BigRational {
+            BigRational::from_integer(n.into())
+        }
+        let mut matrix = vec![
+            vec![r(1), r(2), r(1), r(10)],
+            vec![r(2), r(3), r(2), r(12)],
+            vec![r(3), r(1), r(4), r(11)],
+        ];
+        gauss_jordan_elimination_generic(&mut matrix);
+        assert_eq!(
+            matrix,
+            vec![
+                vec![r(1), r(0), r(0), r(-27)],
+                vec![r(0), r(1), r(0), r(8)],
+                vec![r(0), r(0), r(1), r(21)],
+            ]
+        );
+    }
 }
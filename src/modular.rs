@@ -0,0 +1,203 @@
+// Copyright 2022 Burak Emir
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+
+// An element of the finite field GF(P), held as its canonical residue in
+// [0, P). Every arithmetic operation reduces modulo P, using u128/i128
+// intermediates so products can't overflow u64. Division multiplies by the
+// modular inverse, computed via the extended Euclidean algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mod<const P: u64>(u64);
+
+impl<const P: u64> Mod<P> {
+    pub fn new(value: u64) -> Self {
+        Mod(value % P)
+    }
+
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    // Extended Euclidean algorithm: returns (gcd, x, y) such that
+    // a * x + b * y == gcd.
+    fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+        if a == 0 {
+            (b, 0, 1)
+        } else {
+            let (g, x, y) = Self::extended_gcd(b % a, a);
+            (g, y - (b / a) * x, x)
+        }
+    }
+
+    // The modular inverse of `self`, i.e. the x with self * x == 1 (mod P).
+    // Panics if `self` isn't a unit mod P (not coprime with P, e.g. zero, or
+    // any non-trivial factor of P when P isn't prime) -- same as dividing
+    // by zero on a plain integer type.
+    fn inverse(self) -> Self {
+        let (gcd, x, _) = Self::extended_gcd(self.0 as i128, P as i128);
+        assert!(
+            gcd == 1,
+            "{} has no inverse mod {}: not coprime (gcd = {})",
+            self.0, P, gcd
+        );
+        let p = P as i128;
+        Mod((((x % p) + p) % p) as u64)
+    }
+}
+
+impl<const P: u64> Add for Mod<P> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Mod(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Sub for Mod<P> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Mod(((self.0 as u128 + P as u128 - rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Mul for Mod<P> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Mod(((self.0 as u128 * rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Div for Mod<P> {
+    type Output = Self;
+    // Division is intentionally multiplication by the modular inverse, not
+    // a typo for `Mul`.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl<const P: u64> AddAssign for Mod<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u64> SubAssign for Mod<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u64> MulAssign for Mod<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<'a, const P: u64> Add<&'a Mod<P>> for Mod<P> {
+    type Output = Mod<P>;
+    fn add(self, rhs: &'a Mod<P>) -> Mod<P> {
+        self + *rhs
+    }
+}
+
+impl<'a, const P: u64> Mul<&'a Mod<P>> for Mod<P> {
+    type Output = Mod<P>;
+    fn mul(self, rhs: &'a Mod<P>) -> Mod<P> {
+        self * *rhs
+    }
+}
+
+impl<const P: u64> Mul<Mod<P>> for &Mod<P> {
+    type Output = Mod<P>;
+    fn mul(self, rhs: Mod<P>) -> Mod<P> {
+        *self * rhs
+    }
+}
+
+impl<const P: u64> num::traits::Zero for Mod<P> {
+    fn zero() -> Self {
+        Mod(0)
+    }
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u64> num::traits::One for Mod<P> {
+    fn one() -> Self {
+        Mod(1 % P)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gauss_jordan_elimination_generic;
+
+    #[test]
+    fn test_gauss_jordan_elimination_gf7() {
+        let mut matrix = vec![
+            vec![Mod::<7>::new(1), Mod::new(2), Mod::new(1), Mod::new(10)],
+            vec![Mod::new(2), Mod::new(3), Mod::new(2), Mod::new(12)],
+            vec![Mod::new(3), Mod::new(1), Mod::new(4), Mod::new(11)],
+        ];
+        gauss_jordan_elimination_generic(&mut matrix);
+        // Same system as the f32 tests (solution -27, 8, 21), reduced mod 7.
+        assert_eq!(
+            matrix,
+            vec![
+                vec![Mod::new(1), Mod::new(0), Mod::new(0), Mod::new(1)],
+                vec![Mod::new(0), Mod::new(1), Mod::new(0), Mod::new(1)],
+                vec![Mod::new(0), Mod::new(0), Mod::new(1), Mod::new(0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gauss_jordan_elimination_gf2() {
+        let mut matrix = vec![
+            vec![Mod::<2>::new(1), Mod::new(1), Mod::new(1)],
+            vec![Mod::new(1), Mod::new(0), Mod::new(1)],
+        ];
+        gauss_jordan_elimination_generic(&mut matrix);
+        assert_eq!(
+            matrix,
+            vec![
+                vec![Mod::new(1), Mod::new(0), Mod::new(1)],
+                vec![Mod::new(0), Mod::new(1), Mod::new(0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let a = Mod::<7>::new(3);
+        assert_eq!(a * a.inverse(), Mod::new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_of_zero_panics() {
+        Mod::<7>::new(0).inverse();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_of_non_unit_panics() {
+        // 6 isn't prime, so 2 and 6 share a factor and 2 has no inverse.
+        Mod::<6>::new(2).inverse();
+    }
+}